@@ -10,11 +10,15 @@ use std::hash::Hash;
 use crate::util::{AsBase64, SCALAR_MAX_BYTES};
 use std::convert::{TryFrom, TryInto};
 use crate::threshold::EncodingError;
+use crate::secret::SecretScalar;
 
-#[derive(Copy, Clone)]
+/// `x_i` is wrapped in [`SecretScalar`] rather than left as a bare `Scalar`,
+/// so the pair can no longer be handed out `Copy`: `pk`/`y_i` stay freely
+/// shareable, but a caller can't walk away with a second copy of the private
+/// half just by passing the pair around.
 pub struct KeyPair {
     pub pk: PublicKey,
-    pub x_i: Scalar,
+    pub x_i: SecretScalar,
     pub y_i: CurveElem,
 }
 
@@ -23,7 +27,7 @@ impl KeyPair {
         let x_i = ctx.random_power()?;
         let y_i = ctx.g_to(&x_i);
         let pk = PublicKey::new(y_i);
-        Ok(Self { pk, x_i, y_i })
+        Ok(Self { pk, x_i: SecretScalar::new(x_i), y_i })
     }
 }
 
@@ -95,8 +99,8 @@ impl Ciphertext {
         }
     }
 
-    pub fn decrypt(&self, secret_key: &Scalar) -> CurveElem {
-        &self.c2 - &(self.c1.scaled(secret_key))
+    pub fn decrypt(&self, secret_key: &SecretScalar) -> CurveElem {
+        &self.c2 - &(self.c1.scaled(secret_key.expose()))
     }
 }
 
@@ -148,7 +152,7 @@ impl AuthCiphertext {
         self.hash == hash
     }
 
-    pub fn decrypt(&self, secret_key: &Scalar) -> Option<CurveElem> {
+    pub fn decrypt(&self, secret_key: &SecretScalar) -> Option<CurveElem> {
         let plaintext = self.contents.decrypt(secret_key);
         if self.verify(&plaintext) {
             Some(plaintext)
@@ -238,6 +242,7 @@ impl CryptoContext {
 mod test {
     use crate::elgamal::{CryptoContext, PublicKey, Ciphertext, AuthCiphertext};
     use crate::util::AsBase64;
+    use crate::secret::SecretScalar;
     use std::convert::TryFrom;
 
     #[test]
@@ -288,7 +293,7 @@ mod test {
 
         // Compare the added encryption to the added messages
         let prod = ct1.add(&ct2);
-        // let decryption = &prod.c2 - &(prod.c1.scaled(&x));
+        let x = SecretScalar::new(x);
         let decryption = prod.decrypt(&x);
 
         let combined = &m1 + &m2;
@@ -307,6 +312,7 @@ mod test {
         let m_r = ctx.random_power().unwrap();
         let ct = y.encrypt_auth(&ctx, &m, &m_r);
 
+        let x = SecretScalar::new(x);
         assert_eq!(ct.decrypt(&x).unwrap(), m);
     }
 
@@ -332,6 +338,7 @@ mod test {
             hash: ct.hash.clone(),
         };
 
+        let x = SecretScalar::new(x);
         assert!(!auth_modified.verify(&(m + m_dash)));
         assert_eq!(ct_modified.decrypt(&x), m + m_dash);
     }
@@ -0,0 +1,43 @@
+use std::fmt;
+use zeroize::Zeroize;
+
+use crate::Scalar;
+
+/// A secret scalar that cannot be silently duplicated.
+///
+/// Unlike [`Scalar`], which is freely `Copy` so that curve arithmetic reads
+/// naturally, `SecretScalar` is move-only and zeroes its backing bytes on
+/// [`Drop`] (via [`zeroize`], the same crate `rust-secp256k1` uses for its
+/// own secret keys). It is the only type used to carry ElGamal secret keys
+/// and threshold VSS shares, so that key material can't linger in memory or
+/// get duplicated by an accidental `.clone()` the way a `Copy` `Scalar` would.
+///
+/// `Debug` is hand-written rather than derived, and redacts the wrapped
+/// scalar: a derived impl would print the raw secret in any `{:?}`, panic
+/// message, or trace log that happens to touch this value or a struct
+/// embedding it, which is exactly the accidental-leak class this wrapper
+/// exists to close off.
+pub struct SecretScalar(Scalar);
+
+impl SecretScalar {
+    pub fn new(value: Scalar) -> Self {
+        Self(value)
+    }
+
+    /// Borrows the wrapped scalar for use in curve arithmetic.
+    pub fn expose(&self) -> &Scalar {
+        &self.0
+    }
+}
+
+impl fmt::Debug for SecretScalar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretScalar(REDACTED)")
+    }
+}
+
+impl Drop for SecretScalar {
+    fn drop(&mut self) {
+        self.0.0.zeroize();
+    }
+}
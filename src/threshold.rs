@@ -0,0 +1,473 @@
+use serde::{Serialize, Deserialize};
+
+use crate::{Hasher, Scalar, AsBase64, CryptoError};
+use crate::curve::CurveElem;
+use crate::elgamal::{CryptoContext, PublicKey, Ciphertext};
+use crate::scalar::DalekScalar;
+use crate::secret::SecretScalar;
+use crate::zkp::PrfDecryption;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodingError {
+    CurveElem,
+    Length,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThresholdError {
+    /// A share failed its Feldman VSS verification against the dealer's commitments.
+    InvalidShare,
+    /// Fewer than `t` valid partial decryptions were supplied to the combiner,
+    /// or no dealings were supplied to [`ThresholdKeyShare::combine`].
+    NotEnoughShares,
+    /// Two partial decryptions claimed the same party index.
+    DuplicateIndex,
+}
+
+/// A Feldman verifiable secret sharing of a single dealer's contribution to
+/// the group secret.
+///
+/// The dealer samples a degree-`(t-1)` polynomial `f(x) = a_0 + a_1*x + ... +
+/// a_{t-1}*x^{t-1}`, publishes the coefficient commitments `g^{a_k}`, and
+/// sends party `j` the share `f(j)` privately. Any party can verify its share
+/// against the public commitments without learning the polynomial.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VssDealing {
+    /// `commitments[k] = g^{a_k}`, for `k` in `0..t`.
+    pub commitments: Vec<CurveElem>,
+}
+
+impl VssDealing {
+    /// Samples a fresh degree-`(t-1)` polynomial and returns the dealing
+    /// (public commitments) along with the shares `f(1), ..., f(n)` to be
+    /// sent privately to each of the `n` parties.
+    pub fn deal(ctx: &mut CryptoContext, t: usize, n: usize) -> Result<(Self, Vec<SecretScalar>), CryptoError> {
+        assert!(t >= 1 && t <= n, "threshold must satisfy 1 <= t <= n");
+
+        let mut coeffs = Vec::with_capacity(t);
+        for _ in 0..t {
+            coeffs.push(ctx.random_scalar());
+        }
+
+        let commitments = coeffs.iter()
+            .map(|a_k| ctx.g_to(a_k))
+            .collect();
+
+        let shares = (1..=n)
+            .map(|j| SecretScalar::new(Self::eval(&coeffs, j as u64)))
+            .collect();
+
+        Ok((Self { commitments }, shares))
+    }
+
+    /// Evaluates `f(x) = a_0 + a_1*x + ... + a_{t-1}*x^{t-1}` at `x`.
+    fn eval(coeffs: &[Scalar], x: u64) -> Scalar {
+        let x = Scalar::from(x);
+        let mut acc = DalekScalar::zero();
+        for a_k in coeffs.iter().rev() {
+            acc = acc * x.0 + a_k.0;
+        }
+        Scalar(acc)
+    }
+
+    /// The dealer's contribution to the group public key, `g^{a_0}`.
+    pub fn public_contribution(&self) -> CurveElem {
+        self.commitments[0].clone()
+    }
+
+    /// Verifies that `share` is consistent with this dealing for party `index`
+    /// (1-indexed), i.e. that `g^{share} == prod_k (g^{a_k})^{index^k}`.
+    pub fn verify_share(&self, ctx: &CryptoContext, index: u64, share: &Scalar) -> bool {
+        let lhs = ctx.g_to(share);
+
+        // Horner's method in the exponent, mirroring `eval`'s field-space
+        // Horner loop: fold from the top coefficient down, so `index^k` is
+        // never computed as a standalone (and potentially overflowing)
+        // integer.
+        let x = Scalar::from(index);
+        let mut rhs: Option<CurveElem> = None;
+        for commitment in self.commitments.iter().rev() {
+            rhs = Some(match rhs {
+                None => commitment.clone(),
+                Some(acc) => &acc.scaled(&x) + commitment,
+            });
+        }
+
+        match rhs {
+            Some(rhs) => lhs == rhs,
+            None => false,
+        }
+    }
+}
+
+/// The secret share and verification key held by a single party in a `(t,n)`
+/// threshold ElGamal scheme, after combining the Feldman dealings of every
+/// participant.
+///
+/// Not `Clone`: `share` is this party's slice of the joint secret key, and
+/// [`SecretScalar`] forbids the kind of silent duplication a derived `Clone`
+/// would allow. `Debug` is safe to derive: [`SecretScalar`]'s own `Debug`
+/// impl redacts `share` rather than printing it, so this doesn't leak key
+/// material the way a derive over a bare `Scalar` field would.
+#[derive(Debug)]
+pub struct ThresholdKeyShare {
+    pub index: u64,
+    pub threshold: usize,
+    pub share: SecretScalar,
+    pub verification_key: CurveElem,
+    pub group_public_key: PublicKey,
+}
+
+impl ThresholdKeyShare {
+    /// Combines this party's verified shares from every dealer (one per
+    /// dealer, in the same order as `dealings`) into this party's share of
+    /// the joint secret, and derives the group public key as the product of
+    /// every dealer's `g^{a_0}`.
+    ///
+    /// Returns [`ThresholdError::InvalidShare`] if any share fails Feldman
+    /// verification against its dealing.
+    pub fn combine(ctx: &CryptoContext,
+                   index: u64,
+                   threshold: usize,
+                   dealings: &[VssDealing],
+                   shares: &[SecretScalar]) -> Result<Self, ThresholdError> {
+        assert_eq!(dealings.len(), shares.len());
+        if dealings.is_empty() {
+            return Err(ThresholdError::NotEnoughShares);
+        }
+
+        let mut total_share = DalekScalar::zero();
+        let mut group_public_key: Option<CurveElem> = None;
+
+        for (dealing, share) in dealings.iter().zip(shares) {
+            if !dealing.verify_share(ctx, index, share.expose()) {
+                return Err(ThresholdError::InvalidShare);
+            }
+            total_share = total_share + share.expose().0;
+
+            let contribution = dealing.public_contribution();
+            group_public_key = Some(match group_public_key {
+                None => contribution,
+                Some(acc) => &acc + &contribution,
+            });
+        }
+
+        let share = SecretScalar::new(Scalar(total_share));
+        let verification_key = ctx.g_to(share.expose());
+        let group_public_key = PublicKey::new(group_public_key.expect("at least one dealing is required"));
+
+        Ok(Self { index, threshold, share, verification_key, group_public_key })
+    }
+}
+
+/// A single party's contribution towards decrypting a [`Ciphertext`] under a
+/// threshold public key: the partial decryption factor `c1^{s_i}`, proven
+/// against the party's published verification key `g^{s_i}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialDecryption {
+    pub index: u64,
+    pub dec_factor: CurveElem,
+    pub proof: PrfDecryption,
+}
+
+impl PartialDecryption {
+    /// Produces this party's partial decryption of `ct`, along with a proof
+    /// that `dec_factor = ct.c1^{share}` matches the published
+    /// `verification_key = g^{share}`.
+    pub fn new(ctx: &CryptoContext, key_share: &ThresholdKeyShare, ct: &Ciphertext) -> Self {
+        let dec_factor = ct.c1.scaled(key_share.share.expose());
+        let proof = PrfDecryption::new(ctx, ctx.generator(), ct.c1.clone(), ct.c2.clone(),
+                                        key_share.verification_key.clone(), dec_factor.clone(),
+                                        &key_share.share);
+        Self { index: key_share.index, dec_factor, proof }
+    }
+
+    /// Checks the accompanying proof against the claimed `dec_factor`, the
+    /// party's published `verification_key`, and the `ct` this partial is
+    /// claimed to be for, so a validly-proven partial computed for a
+    /// different ciphertext can't be replayed as if it were for `ct`.
+    pub fn verify(&self, verification_key: &CurveElem, ct: &Ciphertext) -> bool {
+        self.proof.dec_factor == self.dec_factor
+            && &self.proof.public_key == verification_key
+            && self.proof.c1 == ct.c1
+            && self.proof.c2 == ct.c2
+            && self.proof.verify()
+    }
+}
+
+/// The Lagrange coefficient `lambda_i = prod_{j != i} j/(j - i)` for
+/// reconstructing a secret shared among the party indices in `indices`, at
+/// party `i`, evaluated in the exponent at `x = 0`.
+fn lagrange_coefficient(i: u64, indices: &[u64]) -> Scalar {
+    let mut num = DalekScalar::one();
+    let mut den = DalekScalar::one();
+    for &j in indices {
+        if j == i {
+            continue;
+        }
+        num = num * Scalar::from(j).0;
+        den = den * (Scalar::from(j).0 - Scalar::from(i).0);
+    }
+    Scalar(num * den.invert())
+}
+
+/// Combines the first `t` valid [`PartialDecryption`]s into the decrypted
+/// message, recovering `m = c2 - sum(lambda_i * c1^{s_i})` via Lagrange
+/// interpolation in the exponent.
+///
+/// `verification_keys` must map each partial's `index` to the `g^{s_i}`
+/// published for that party (e.g. from [`ThresholdKeyShare::verification_key`]).
+/// Partials with an unknown index or a failing proof are skipped; a partial
+/// repeating the index of one already accepted returns
+/// [`ThresholdError::DuplicateIndex`]. Scanning stops once `t` partials are
+/// accepted, and fewer than `t` returns [`ThresholdError::NotEnoughShares`].
+pub fn combine_partial_decryptions(ct: &Ciphertext,
+                                    t: usize,
+                                    partials: &[PartialDecryption],
+                                    verification_keys: &[(u64, CurveElem)]) -> Result<CurveElem, ThresholdError> {
+    if t < 1 {
+        return Err(ThresholdError::NotEnoughShares);
+    }
+
+    let mut valid = Vec::new();
+    let mut seen = Vec::new();
+
+    for partial in partials {
+        if seen.contains(&partial.index) {
+            return Err(ThresholdError::DuplicateIndex);
+        }
+
+        let key = verification_keys.iter()
+            .find(|(idx, _)| *idx == partial.index)
+            .map(|(_, key)| key);
+
+        // An unknown index or a failed proof marks only this one partial as
+        // unusable: a single bad or malicious submission must not deny
+        // decryption when `t` honest partials are present elsewhere in the
+        // slice, so skip it and keep scanning rather than aborting.
+        let key = match key {
+            Some(key) => key,
+            None => continue,
+        };
+
+        if !partial.verify(key, ct) {
+            continue;
+        }
+
+        seen.push(partial.index);
+        valid.push(partial);
+        if valid.len() == t {
+            break;
+        }
+    }
+
+    if valid.len() < t {
+        return Err(ThresholdError::NotEnoughShares);
+    }
+
+    let indices: Vec<u64> = valid.iter().map(|p| p.index).collect();
+
+    let mut acc: Option<CurveElem> = None;
+    for partial in &valid {
+        let lambda = lagrange_coefficient(partial.index, &indices);
+        let term = partial.dec_factor.scaled(&lambda);
+        acc = Some(match acc {
+            None => term,
+            Some(sum) => &sum + &term,
+        });
+    }
+
+    let combined = acc.expect("t >= 1 guarantees at least one term");
+    Ok(&ct.c2 - &combined)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::elgamal::CryptoContext;
+    use crate::threshold::{VssDealing, ThresholdKeyShare, ThresholdError, PartialDecryption, combine_partial_decryptions};
+    use crate::secret::SecretScalar;
+    use crate::Scalar;
+    use crate::scalar::DalekScalar;
+
+    #[test]
+    fn test_threshold_decryption() {
+        let mut ctx = CryptoContext::new();
+        let (t, n) = (3, 5);
+
+        // Each of the n parties deals a VSS of their own random polynomial.
+        // Shares are `SecretScalar`s, so each one is consumed exactly once:
+        // stash them in a `VecDeque` per dealer and pop one off per party.
+        let mut dealings = Vec::new();
+        let mut all_shares: Vec<std::collections::VecDeque<_>> = Vec::new();
+        for _ in 0..n {
+            let (dealing, shares) = VssDealing::deal(&mut ctx, t, n).unwrap();
+            dealings.push(dealing);
+            all_shares.push(shares.into_iter().collect());
+        }
+
+        // Party i (1-indexed) combines the i-th share from every dealing.
+        let mut key_shares = Vec::new();
+        for i in 1..=n as u64 {
+            let shares_for_i: Vec<_> = all_shares.iter_mut()
+                .map(|shares| shares.pop_front().unwrap())
+                .collect();
+            let key_share = ThresholdKeyShare::combine(&ctx, i, t, &dealings, &shares_for_i).unwrap();
+            key_shares.push(key_share);
+        }
+
+        // All parties should agree on the group public key.
+        let group_pk = key_shares[0].group_public_key.clone();
+        assert!(key_shares.iter().all(|ks| ks.group_public_key == group_pk));
+
+        let m = ctx.random_elem();
+        let r = ctx.random_scalar();
+        let ct = group_pk.encrypt(&ctx, &m, &r);
+
+        let verification_keys: Vec<_> = key_shares.iter()
+            .map(|ks| (ks.index, ks.verification_key.clone()))
+            .collect();
+
+        // Only t of the n parties participate.
+        let partials: Vec<_> = key_shares.iter().take(t)
+            .map(|ks| PartialDecryption::new(&ctx, ks, &ct))
+            .collect();
+
+        let decrypted = combine_partial_decryptions(&ct, t, &partials, &verification_keys).unwrap();
+        assert_eq!(decrypted, m);
+    }
+
+    #[test]
+    fn test_combine_rejects_invalid_share() {
+        let mut ctx = CryptoContext::new();
+        let (t, n) = (3, 5);
+
+        let (dealing, shares) = VssDealing::deal(&mut ctx, t, n).unwrap();
+        let mut tampered = shares[0].expose().clone();
+        tampered.0 += &DalekScalar::one();
+
+        let result = ThresholdKeyShare::combine(&ctx, 1, t, &[dealing], &[SecretScalar::new(tampered)]);
+        assert_eq!(result.err(), Some(ThresholdError::InvalidShare));
+    }
+
+    #[test]
+    fn test_combine_partial_decryptions_rejects_duplicate_index() {
+        let mut ctx = CryptoContext::new();
+        let (t, n) = (3, 5);
+
+        let mut dealings = Vec::new();
+        let mut all_shares: Vec<std::collections::VecDeque<_>> = Vec::new();
+        for _ in 0..n {
+            let (dealing, shares) = VssDealing::deal(&mut ctx, t, n).unwrap();
+            dealings.push(dealing);
+            all_shares.push(shares.into_iter().collect());
+        }
+
+        let mut key_shares = Vec::new();
+        for i in 1..=n as u64 {
+            let shares_for_i: Vec<_> = all_shares.iter_mut()
+                .map(|shares| shares.pop_front().unwrap())
+                .collect();
+            key_shares.push(ThresholdKeyShare::combine(&ctx, i, t, &dealings, &shares_for_i).unwrap());
+        }
+
+        let group_pk = key_shares[0].group_public_key.clone();
+        let m = ctx.random_elem();
+        let r = ctx.random_scalar();
+        let ct = group_pk.encrypt(&ctx, &m, &r);
+
+        let verification_keys: Vec<_> = key_shares.iter()
+            .map(|ks| (ks.index, ks.verification_key.clone()))
+            .collect();
+
+        // Party 1's partial, submitted twice under the same index.
+        let partial = PartialDecryption::new(&ctx, &key_shares[0], &ct);
+        let partials = vec![partial.clone(), partial];
+
+        let result = combine_partial_decryptions(&ct, t, &partials, &verification_keys);
+        assert_eq!(result.err(), Some(ThresholdError::DuplicateIndex));
+    }
+
+    #[test]
+    fn test_combine_partial_decryptions_rejects_wrong_ciphertext() {
+        let mut ctx = CryptoContext::new();
+        let (t, n) = (3, 5);
+
+        let mut dealings = Vec::new();
+        let mut all_shares: Vec<std::collections::VecDeque<_>> = Vec::new();
+        for _ in 0..n {
+            let (dealing, shares) = VssDealing::deal(&mut ctx, t, n).unwrap();
+            dealings.push(dealing);
+            all_shares.push(shares.into_iter().collect());
+        }
+
+        let mut key_shares = Vec::new();
+        for i in 1..=n as u64 {
+            let shares_for_i: Vec<_> = all_shares.iter_mut()
+                .map(|shares| shares.pop_front().unwrap())
+                .collect();
+            key_shares.push(ThresholdKeyShare::combine(&ctx, i, t, &dealings, &shares_for_i).unwrap());
+        }
+
+        let group_pk = key_shares[0].group_public_key.clone();
+        let m = ctx.random_elem();
+        let r = ctx.random_scalar();
+        let ct = group_pk.encrypt(&ctx, &m, &r);
+
+        // A different ciphertext under the same group key.
+        let other_m = ctx.random_elem();
+        let other_r = ctx.random_scalar();
+        let other_ct = group_pk.encrypt(&ctx, &other_m, &other_r);
+
+        let verification_keys: Vec<_> = key_shares.iter()
+            .map(|ks| (ks.index, ks.verification_key.clone()))
+            .collect();
+
+        // Partials are validly proven for `other_ct`, but resubmitted as
+        // partials for `ct`: they must not be accepted as decryptions of `ct`.
+        let partials: Vec<_> = key_shares.iter().take(t)
+            .map(|ks| PartialDecryption::new(&ctx, ks, &other_ct))
+            .collect();
+
+        let result = combine_partial_decryptions(&ct, t, &partials, &verification_keys);
+        assert_eq!(result.err(), Some(ThresholdError::NotEnoughShares));
+    }
+
+    #[test]
+    fn test_combine_partial_decryptions_not_enough_shares() {
+        let mut ctx = CryptoContext::new();
+        let (t, n) = (3, 5);
+
+        let mut dealings = Vec::new();
+        let mut all_shares: Vec<std::collections::VecDeque<_>> = Vec::new();
+        for _ in 0..n {
+            let (dealing, shares) = VssDealing::deal(&mut ctx, t, n).unwrap();
+            dealings.push(dealing);
+            all_shares.push(shares.into_iter().collect());
+        }
+
+        let mut key_shares = Vec::new();
+        for i in 1..=n as u64 {
+            let shares_for_i: Vec<_> = all_shares.iter_mut()
+                .map(|shares| shares.pop_front().unwrap())
+                .collect();
+            key_shares.push(ThresholdKeyShare::combine(&ctx, i, t, &dealings, &shares_for_i).unwrap());
+        }
+
+        let group_pk = key_shares[0].group_public_key.clone();
+        let m = ctx.random_elem();
+        let r = ctx.random_scalar();
+        let ct = group_pk.encrypt(&ctx, &m, &r);
+
+        let verification_keys: Vec<_> = key_shares.iter()
+            .map(|ks| (ks.index, ks.verification_key.clone()))
+            .collect();
+
+        // Fewer than t partials participate.
+        let partials: Vec<_> = key_shares.iter().take(t - 1)
+            .map(|ks| PartialDecryption::new(&ctx, ks, &ct))
+            .collect();
+
+        let result = combine_partial_decryptions(&ct, t, &partials, &verification_keys);
+        assert_eq!(result.err(), Some(ThresholdError::NotEnoughShares));
+    }
+}
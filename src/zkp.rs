@@ -3,180 +3,442 @@ use serde::{Serialize, Deserialize};
 use crate::{Hasher, Scalar, AsBase64};
 use crate::curve::CurveElem;
 use crate::elgamal::{CryptoContext, Ciphertext};
+use crate::scalar::DalekScalar;
+use crate::secret::SecretScalar;
 use std::fmt::Display;
 use serde::export::Formatter;
 
-const KNOW_PLAINTEXT_TAG: &'static str = "KNOW_PLAINTEXT";
+/// Declares a Fiat-Shamir sigma-protocol proof for a set of public linear
+/// relations `result_k = base_k^r` over [`CurveElem`]s that all share the
+/// same secret scalar `r`.
+///
+/// This generates the proof struct (one field per public point, plus the
+/// per-equation commitments and the single response scalar), a domain-tagged
+/// Fiat-Shamir `challenge`, and `new`/`verify` following the commit/challenge/
+/// respond pattern every hand-written proof in this module used to repeat:
+/// commit with a random `z` to `base_k^z`, derive `c = H(publics, commitments,
+/// tag)`, respond with `r = z + c*secret`, and on verify check `base_k^r ==
+/// commitment_k + result_k^c` for every equation.
+macro_rules! define_proof {
+    (
+        $(#[$doc:meta])*
+        $name:ident,
+        tag: $tag:expr,
+        public: { $($pname:ident),+ $(,)? },
+        equations: { $($result:ident = $base:ident ^ r),+ $(,)? }
+    ) => {
+        $(#[$doc])*
+        #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+        pub struct $name {
+            $(pub $pname: CurveElem,)+
+            commitments: Vec<CurveElem>,
+            r: Scalar,
+        }
 
-#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
-pub struct PrfKnowPlaintext {
-    pub g: CurveElem,
-    pub ct: Ciphertext,
-    blinded_g: CurveElem,
-    r: Scalar,
+        impl $name {
+            fn challenge($($pname: &CurveElem,)+ commitments: &[CurveElem]) -> Scalar {
+                let mut hasher = Hasher::sha_256();
+                $(hasher = hasher.and_update(&$pname.as_bytes());)+
+                for commitment in commitments {
+                    hasher = hasher.and_update(&commitment.as_bytes());
+                }
+                hasher.and_update($tag.as_bytes()).finish_scalar()
+            }
+
+            pub fn new(ctx: &CryptoContext, $($pname: CurveElem,)+ secret: &SecretScalar) -> Self {
+                let z = ctx.random_scalar();
+                let commitments = vec![$($base.scaled(&z)),+];
+                let c = Self::challenge($(&$pname,)+ &commitments);
+                let r = Scalar(z.0 + c.0 * secret.expose().0);
+
+                Self { $($pname,)+ commitments, r }
+            }
+
+            pub fn verify(&self) -> bool {
+                let expected = [$(stringify!($result)),+].len();
+                if self.commitments.len() != expected {
+                    return false;
+                }
+
+                let c = Self::challenge($(&self.$pname,)+ &self.commitments);
+
+                let mut idx = 0;
+                $(
+                    let holds = self.$base.scaled(&self.r) == &self.commitments[idx] + &self.$result.scaled(&c);
+                    idx += 1;
+                    if !holds {
+                        return false;
+                    }
+                )+
+                true
+            }
+
+            /// Checks a whole slice of proofs via a single random linear
+            /// combination instead of `n` independent equality checks:
+            /// samples an independent random weight `delta_{i,k}` per proof
+            /// *and* per equation, recomputes each proof's challenge `c_i`,
+            /// and checks `sum(delta_{i,k} * base_k^r_i) == sum(delta_{i,k} *
+            /// (commitment_k + result_k^c_i))` over every proof and equation
+            /// at once. Weighting per equation (not just per proof) is
+            /// required: with a single weight per proof, a forger can pick
+            /// commitments for which the per-equation residuals are
+            /// individually nonzero but cancel each other out in the weighted
+            /// sum regardless of the weight, slipping a forged proof past the
+            /// batch check even though it would fail [`Self::verify`]. With an
+            /// independent weight per equation, a forged proof only slips
+            /// through with negligible probability, since it would need to
+            /// cancel out against the others under unknown random weights.
+            ///
+            /// This is *not* a throughput optimization over calling
+            /// [`Self::verify`] in a loop: weighting each equation's
+            /// commitment independently (needed for soundness, above) costs
+            /// one extra `scaled()` call per equation that plain `verify`
+            /// doesn't pay, so this does strictly more scalar multiplications
+            /// than the loop it replaces — each term here is still computed
+            /// with its own double-and-add, there's no multi-scalar-multiplication
+            /// primitive sharing work across terms. The payoff is a single
+            /// aggregate pass/fail instead of `n` separate branches, which is
+            /// useful when you only need to know whether an entire batch is
+            /// clean (falling back to [`Self::verify_each`] to localize a
+            /// failure) — not when throughput on large batches is the goal.
+            pub fn verify_batch(proofs: &[Self]) -> bool {
+                if proofs.is_empty() {
+                    return true;
+                }
+
+                let expected = [$(stringify!($result)),+].len();
+                if proofs.iter().any(|proof| proof.commitments.len() != expected) {
+                    return false;
+                }
+
+                let weight_ctx = CryptoContext::new();
+
+                let mut lhs: Option<CurveElem> = None;
+                let mut rhs: Option<CurveElem> = None;
+
+                for proof in proofs {
+                    let c = Self::challenge($(&proof.$pname,)+ &proof.commitments);
+
+                    let mut idx = 0;
+                    $(
+                        let delta = weight_ctx.random_scalar();
+                        let base_term = proof.$base.scaled(&Scalar(proof.r.0 * delta.0));
+                        let result_term = &proof.commitments[idx].scaled(&delta)
+                            + &proof.$result.scaled(&Scalar(c.0 * delta.0));
+                        idx += 1;
+
+                        lhs = Some(match lhs {
+                            None => base_term,
+                            Some(acc) => &acc + &base_term,
+                        });
+                        rhs = Some(match rhs {
+                            None => result_term,
+                            Some(acc) => &acc + &result_term,
+                        });
+                    )+
+                }
+
+                match (lhs, rhs) {
+                    (Some(lhs), Some(rhs)) => lhs == rhs,
+                    _ => false,
+                }
+            }
+
+            /// Falls back to checking each proof individually, to identify
+            /// which element of a failed [`Self::verify_batch`] call is
+            /// invalid.
+            pub fn verify_each(proofs: &[Self]) -> Vec<bool> {
+                proofs.iter().map(Self::verify).collect()
+            }
+        }
+    };
 }
 
-impl Display for PrfKnowPlaintext {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}:{}:{}:{}", self.g.as_base64(), self.ct.to_string(),
-               self.blinded_g.as_base64(), self.r.as_base64())
-    }
+define_proof! {
+    /// Proves knowledge of the randomizer `r` used to encrypt a ciphertext,
+    /// i.e. knowledge of the plaintext, since decryption is then just
+    /// `c2 - y^r`.
+    PrfKnowPlaintext,
+    tag: "KNOW_PLAINTEXT",
+    public: { g, c1, c2 },
+    equations: { c1 = g ^ r }
 }
 
-impl PrfKnowPlaintext {
-    fn challenge(g: &CurveElem, ct: &Ciphertext, blinded_g: &CurveElem) -> Scalar {
-        Hasher::sha_256()
-            .and_update(&g.as_bytes())
-            .and_update(&ct.c1.as_bytes())
-            .and_update(&ct.c2.as_bytes())
-            .and_update(&blinded_g.as_bytes())
-            .and_update(KNOW_PLAINTEXT_TAG.as_bytes())
-            .finish_scalar()
-    }
+define_proof! {
+    /// Proves that `result1 = base1^x` and `result2 = base2^x` for the same
+    /// secret `x`, i.e. that `dlog_base1(result1) == dlog_base2(result2)`.
+    PrfEqDlogs,
+    tag: "EQ_DLOGS",
+    public: { base1, base2, result1, result2 },
+    equations: { result1 = base1 ^ r, result2 = base2 ^ r }
+}
 
-    pub fn new(ctx: &CryptoContext, ct: Ciphertext, r: Scalar) -> Self {
-        // Choose random commitment
-        let g = ctx.generator();
-        let z = ctx.random_scalar();
-        let blinded_g = g.scaled(&z);
-        // Calculate the challenge
-        let c = Self::challenge(&g, &ct, &blinded_g);
-        let r = Scalar(z.0 + c.0 * r.0);
+define_proof! {
+    /// Proves that `dec_factor = c1^x` for the same secret `x` behind
+    /// `public_key = g^x`, i.e. that a claimed decryption factor was raised
+    /// to the correct secret key.
+    PrfDecryption,
+    tag: "DECRYPTION",
+    public: { g, c1, c2, public_key, dec_factor },
+    equations: { public_key = g ^ r, dec_factor = c1 ^ r }
+}
 
-        Self { g, ct, blinded_g, r }
+impl Display for PrfKnowPlaintext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}:{}", self.g.as_base64(), self.c1.as_base64(),
+               self.c2.as_base64(), self.r.as_base64())
     }
+}
 
-    pub fn verify(&self) -> bool {
-        let c = Self::challenge(&self.g, &self.ct, &self.blinded_g);
-        self.g.scaled(&self.r) == &self.blinded_g + &self.ct.c1.scaled(&c)
+impl Display for PrfEqDlogs {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}:{}:{}", self.result1.as_base64(), self.base1.as_base64(),
+               self.result2.as_base64(), self.base2.as_base64(), self.r.as_base64())
     }
 }
 
+const ONE_OF_MANY_TAG: &'static str = "ONE_OF_MANY";
+
+/// The per-branch commitment/response pair of a [`PrfOneOfMany`] OR-proof.
+///
+/// For the branch the prover actually knows the witness for, `c` and `r` are
+/// the real Fiat-Shamir challenge share and response. For every other branch
+/// they are simulated: `c` is sampled freely and `r` is sampled freely, and
+/// the commitments are solved backwards from them.
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
-pub struct PrfEqDlogs {
-    pub result1: CurveElem,
-    pub base1: CurveElem,
-    pub result2: CurveElem,
-    pub base2: CurveElem,
-    blinded_base1: CurveElem,
-    blinded_base2: CurveElem,
+struct OrBranch {
+    blinded_c1: CurveElem,
+    blinded_c2: CurveElem,
+    c: Scalar,
     r: Scalar,
 }
 
-impl Display for PrfEqDlogs {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}:{}:{}:{}:{}:{}:{}", self.result1.as_base64(), self.base1.as_base64(),
-               self.result2.as_base64(), self.base2.as_base64(), self.blinded_base1.as_base64(),
-               self.blinded_base2.as_base64(), self.r.as_base64())
-    }
+/// Proves that a [`Ciphertext`] encrypted under `y` decrypts to one of the
+/// `plaintexts`, without revealing which, using the Cramer-Damgård-Schoenmakers
+/// OR-composition of Chaum-Pedersen proofs.
+///
+/// This is the building block for accepting an encrypted choice (e.g.
+/// encryption-of-zero-or-one) into a ballot without learning the vote.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PrfOneOfMany {
+    pub g: CurveElem,
+    pub y: CurveElem,
+    pub ct: Ciphertext,
+    pub plaintexts: Vec<CurveElem>,
+    branches: Vec<OrBranch>,
 }
 
-const EQ_DLOGS_TAG: &'static str = "EQ_DLOGS";
-
-impl PrfEqDlogs {
-    fn challenge(f: &CurveElem,
-                 h: &CurveElem,
-                 v: &CurveElem,
-                 w: &CurveElem,
-                 a: &CurveElem,
-                 b: &CurveElem) -> Scalar {
-        Hasher::sha_256()
-            .and_update(&f.as_bytes())
-            .and_update(&h.as_bytes())
-            .and_update(&v.as_bytes())
-            .and_update(&w.as_bytes())
-            .and_update(&a.as_bytes())
-            .and_update(&b.as_bytes())
-            .and_update(EQ_DLOGS_TAG.as_bytes())
+impl PrfOneOfMany {
+    fn challenge(g: &CurveElem,
+                 y: &CurveElem,
+                 ct: &Ciphertext,
+                 plaintexts: &[CurveElem],
+                 blinded_c1s: &[CurveElem],
+                 blinded_c2s: &[CurveElem]) -> Scalar {
+        let mut hasher = Hasher::sha_256()
+            .and_update(&g.as_bytes())
+            .and_update(&y.as_bytes())
+            .and_update(&ct.c1.as_bytes())
+            .and_update(&ct.c2.as_bytes());
+        for m in plaintexts {
+            hasher = hasher.and_update(&m.as_bytes());
+        }
+        for (a1, a2) in blinded_c1s.iter().zip(blinded_c2s) {
+            hasher = hasher.and_update(&a1.as_bytes())
+                .and_update(&a2.as_bytes());
+        }
+        hasher.and_update(ONE_OF_MANY_TAG.as_bytes())
             .finish_scalar()
     }
 
-    /// Prove that v = f^x and w = h^x, i.e. that dlog_f v = dlog_h w for a secret x
+    /// Proves that `ct` encrypts `plaintexts[index]` under `y`, given the
+    /// randomizer `r` used to construct `ct = (g^r, plaintexts[index]*y^r)`.
     pub fn new(ctx: &CryptoContext,
-               base1: &CurveElem,
-               base2: &CurveElem,
-               result1: &CurveElem,
-               result2: &CurveElem,
-               power: &Scalar) -> Self {
-        let z = ctx.random_scalar();
-        let blinded_base1 = base1.scaled(&z);
-        let blinded_base2 = base2.scaled(&z);
-        let c = Self::challenge(&base1, &base2, &result1, &result2, &blinded_base1, &blinded_base2);
-        let r = Scalar(z.0 + c.0 * power.0);
-        Self {
-            result1: result1.clone(),
-            base1: base1.clone(),
-            result2: result2.clone(),
-            base2: base2.clone(),
-            blinded_base1,
-            blinded_base2,
-            r
+               ct: Ciphertext,
+               y: CurveElem,
+               plaintexts: Vec<CurveElem>,
+               index: usize,
+               r: &Scalar) -> Self {
+        assert!(index < plaintexts.len(), "index out of range of plaintexts");
+
+        let g = ctx.generator();
+        let n = plaintexts.len();
+
+        let mut blinded_c1s = Vec::with_capacity(n);
+        let mut blinded_c2s = Vec::with_capacity(n);
+        let mut cs = Vec::with_capacity(n);
+        let mut rs = Vec::with_capacity(n);
+
+        for j in 0..n {
+            if j == index {
+                let z = ctx.random_scalar();
+                blinded_c1s.push(g.scaled(&z));
+                blinded_c2s.push(y.scaled(&z));
+                cs.push(z); // placeholder, overwritten once the real challenge share is known
+                rs.push(z); // placeholder, overwritten below
+            } else {
+                let c_j = ctx.random_scalar();
+                let r_j = ctx.random_scalar();
+                // The "result" for this branch's y^r equation is c2/m_j.
+                let target_j = &ct.c2 - &plaintexts[j];
+                let blinded_c1 = &g.scaled(&r_j) - &ct.c1.scaled(&c_j);
+                let blinded_c2 = &y.scaled(&r_j) - &target_j.scaled(&c_j);
+                blinded_c1s.push(blinded_c1);
+                blinded_c2s.push(blinded_c2);
+                cs.push(c_j);
+                rs.push(r_j);
+            }
+        }
+
+        let c = Self::challenge(&g, &y, &ct, &plaintexts, &blinded_c1s, &blinded_c2s);
+
+        let mut sum_others = Scalar(DalekScalar::zero());
+        for (j, c_j) in cs.iter().enumerate() {
+            if j != index {
+                sum_others = Scalar(sum_others.0 + c_j.0);
+            }
         }
+        let c_true = Scalar(c.0 - sum_others.0);
+        let z = rs[index];
+        cs[index] = c_true;
+        rs[index] = Scalar(z.0 + c_true.0 * r.0);
+
+        let branches = (0..n).map(|j| OrBranch {
+            blinded_c1: blinded_c1s[j].clone(),
+            blinded_c2: blinded_c2s[j].clone(),
+            c: cs[j],
+            r: rs[j],
+        }).collect();
+
+        Self { g, y, ct, plaintexts, branches }
     }
 
     pub fn verify(&self) -> bool {
-        let c = Self::challenge(&self.base1, &self.base2, &self.result1, &self.result2, &self.blinded_base1, &self.blinded_base2);
-        self.base1.scaled(&self.r) == &self.blinded_base1 + &self.result1.scaled(&c)
-            && self.base2.scaled(&self.r) == &self.blinded_base2 + &self.result2.scaled(&c)
+        if self.plaintexts.len() != self.branches.len() {
+            return false;
+        }
+
+        let blinded_c1s: Vec<_> = self.branches.iter().map(|b| b.blinded_c1.clone()).collect();
+        let blinded_c2s: Vec<_> = self.branches.iter().map(|b| b.blinded_c2.clone()).collect();
+        let c = Self::challenge(&self.g, &self.y, &self.ct, &self.plaintexts, &blinded_c1s, &blinded_c2s);
+
+        let mut sum = Scalar(DalekScalar::zero());
+        for branch in &self.branches {
+            sum = Scalar(sum.0 + branch.c.0);
+        }
+        if sum.0 != c.0 {
+            return false;
+        }
+
+        for (branch, m) in self.branches.iter().zip(&self.plaintexts) {
+            let target = &self.ct.c2 - m;
+            if self.g.scaled(&branch.r) != &branch.blinded_c1 + &self.ct.c1.scaled(&branch.c) {
+                return false;
+            }
+            if self.y.scaled(&branch.r) != &branch.blinded_c2 + &target.scaled(&branch.c) {
+                return false;
+            }
+        }
+
+        true
     }
 }
 
-const DECRYPTION_TAG: &'static str = "DECRYPTION";
-
+/// Proves that a list of `n` [`Ciphertext`]s, all under the same public key
+/// `y`, encrypts a unit vector: exactly one component is an encryption of
+/// `g^1` and every other component is an encryption of `g^0`.
+///
+/// This is exactly what's needed to accept an encrypted choice into a
+/// homomorphic tally without learning which option was selected: each
+/// component is proven to be zero-or-one via [`PrfOneOfMany`], and the
+/// homomorphic sum of all components is proven to encrypt `g^1` via
+/// [`PrfEqDlogs`], so the aggregated tally decrypts to the sum of selections.
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
-pub struct PrfDecryption {
+pub struct PrfUnitVector {
     pub g: CurveElem,
-    pub ct: Ciphertext,
-    pub public_key: CurveElem,
-    pub dec_factor: CurveElem,
-    blinded_g: CurveElem,
-    blinded_c1: CurveElem,
-    r: Scalar,
+    pub y: CurveElem,
+    pub cts: Vec<Ciphertext>,
+    component_proofs: Vec<PrfOneOfMany>,
+    sum_proof: PrfEqDlogs,
 }
 
-impl PrfDecryption {
-    fn challenge(g: &CurveElem, ct: &Ciphertext, dec_factor: &CurveElem, public_key: &CurveElem) -> Scalar {
-        Hasher::sha_256()
-            .and_update(&g.as_bytes())
-            .and_update(&ct.c1.as_bytes())
-            .and_update(&ct.c2.as_bytes())
-            .and_update(&dec_factor.as_bytes())
-            .and_update(&public_key.as_bytes())
-            .and_update(DECRYPTION_TAG.as_bytes())
-            .finish_scalar()
+impl PrfUnitVector {
+    fn aggregate(cts: &[Ciphertext]) -> Ciphertext {
+        let mut iter = cts.iter();
+        let first = iter.next().expect("unit vector must have at least one component").clone();
+        iter.fold(first, |acc, ct| acc.add(ct))
     }
 
-    pub fn new(ctx: &CryptoContext, ct: Ciphertext, dec_factor: CurveElem, secret: Scalar, public_key: CurveElem) -> Self {
-        let g = ctx.generator();
+    /// Proves that `cts` encodes a unit vector with the `1` at `index`, given
+    /// the randomizer used to encrypt each component.
+    pub fn new(ctx: &CryptoContext,
+               y: CurveElem,
+               cts: Vec<Ciphertext>,
+               index: usize,
+               randomizers: &[Scalar]) -> Self {
+        assert_eq!(cts.len(), randomizers.len(), "one randomizer is required per component");
+        assert!(index < cts.len(), "index out of range of cts");
 
-        let z = ctx.random_scalar();
-        let blinded_g = g.scaled(&z);
-        let blinded_c1 = ct.c1.scaled(&z);
+        let g = ctx.generator();
+        let zero = g.scaled(&Scalar(DalekScalar::zero()));
+        let candidates = vec![zero, g.clone()];
+
+        let component_proofs = cts.iter().zip(randomizers).enumerate()
+            .map(|(i, (ct, r))| {
+                let bit = if i == index { 1 } else { 0 };
+                PrfOneOfMany::new(ctx, ct.clone(), y.clone(), candidates.clone(), bit, r)
+            })
+            .collect();
+
+        let agg = Self::aggregate(&cts);
+        let sum_r = randomizers.iter()
+            .fold(Scalar(DalekScalar::zero()), |acc, r| Scalar(acc.0 + r.0));
+        let result2 = &agg.c2 - &g;
+        let sum_proof = PrfEqDlogs::new(ctx, g.clone(), y.clone(), agg.c1.clone(), result2, &SecretScalar::new(sum_r));
+
+        Self { g, y, cts, component_proofs, sum_proof }
+    }
 
-        let c = Self::challenge(&g, &ct, &dec_factor, &public_key);
+    pub fn verify(&self) -> bool {
+        if self.cts.is_empty() || self.cts.len() != self.component_proofs.len() {
+            return false;
+        }
 
-        let r = Scalar(z.0 + c.0 * secret.0);
+        let zero = self.g.scaled(&Scalar(DalekScalar::zero()));
+        for (ct, proof) in self.cts.iter().zip(&self.component_proofs) {
+            if &proof.g != &self.g || &proof.y != &self.y || &proof.ct != ct {
+                return false;
+            }
+            if proof.plaintexts != vec![zero.clone(), self.g.clone()] {
+                return false;
+            }
+            if !proof.verify() {
+                return false;
+            }
+        }
 
-        Self { g, ct, public_key, dec_factor, blinded_g, blinded_c1, r }
-    }
+        let agg = Self::aggregate(&self.cts);
+        let result2 = &agg.c2 - &self.g;
+        if self.sum_proof.base1 != self.g || self.sum_proof.base2 != self.y
+            || self.sum_proof.result1 != agg.c1 || self.sum_proof.result2 != result2 {
+            return false;
+        }
 
-    pub fn verify(&self) -> bool {
-        let c = Self::challenge(&self.g, &self.ct, &self.dec_factor, &self.public_key);
-        self.g.scaled(&self.r) == &self.blinded_g + &self.public_key.scaled(&c)
-            && self.ct.c1.scaled(&self.r) == &self.blinded_c1 + &self.dec_factor.scaled(&c)
+        self.sum_proof.verify()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::elgamal::{CryptoContext, PublicKey};
-    use crate::zkp::{PrfEqDlogs, PrfDecryption, PrfKnowPlaintext};
+    use crate::zkp::{PrfEqDlogs, PrfDecryption, PrfKnowPlaintext, PrfOneOfMany, PrfUnitVector};
     use crate::Scalar;
     use crate::scalar::DalekScalar;
+    use crate::secret::SecretScalar;
 
     #[test]
     fn test_exp_sum() {
-        let ctx = CryptoContext::new().unwrap();
+        let ctx = CryptoContext::new();
         let a = ctx.random_scalar();
         let b = ctx.random_scalar();
         let r = Scalar(a.0 + b.0);
@@ -188,7 +450,7 @@ mod tests {
 
     #[test]
     fn test_prf_know_plaintext_complete() {
-        let ctx = CryptoContext::new().unwrap();
+        let ctx = CryptoContext::new();
         let x = ctx.random_scalar();
         let pk = PublicKey::new(ctx.g_to(&x));
 
@@ -196,13 +458,13 @@ mod tests {
         let r = ctx.random_scalar();
         let enc = pk.encrypt(&ctx, &m, &r);
 
-        let proof = PrfKnowPlaintext::new(&ctx, enc, r);
+        let proof = PrfKnowPlaintext::new(&ctx, ctx.generator(), enc.c1, enc.c2, &SecretScalar::new(r));
         assert!(proof.verify());
     }
 
     #[test]
     fn test_prf_know_plaintext_sound() {
-        let ctx = CryptoContext::new().unwrap();
+        let ctx = CryptoContext::new();
         let x = ctx.random_scalar();
         let pk = PublicKey::new(ctx.g_to(&x));
 
@@ -210,14 +472,14 @@ mod tests {
         let r = ctx.random_scalar();
         let enc = pk.encrypt(&ctx, &m, &r);
 
-        let mut proof = PrfKnowPlaintext::new(&ctx, enc, r);
+        let mut proof = PrfKnowPlaintext::new(&ctx, ctx.generator(), enc.c1, enc.c2, &SecretScalar::new(r));
         proof.r.0 += &DalekScalar::one();
         assert!(!proof.verify());
     }
 
     #[test]
     fn test_prf_eq_dlogs_complete() {
-        let ctx = CryptoContext::new().unwrap();
+        let ctx = CryptoContext::new();
         let x1 = ctx.random_scalar();
         let f = ctx.g_to(&x1);
         let x2 = ctx.random_scalar();
@@ -227,13 +489,13 @@ mod tests {
         let v = f.scaled(&x);
         let w = h.scaled(&x);
 
-        let proof = PrfEqDlogs::new(&ctx, &f, &h, &v, &w, &x);
+        let proof = PrfEqDlogs::new(&ctx, f, h, v, w, &SecretScalar::new(x));
         assert!(proof.verify());
     }
 
     #[test]
     fn test_prf_eq_dlogs_sound() {
-        let ctx = CryptoContext::new().unwrap();
+        let ctx = CryptoContext::new();
         let x1 = ctx.random_scalar();
         let f = ctx.g_to(&x1);
         let x2 = ctx.random_scalar();
@@ -243,7 +505,7 @@ mod tests {
         let v = f.scaled(&x);
         let w = h.scaled(&x);
 
-        let mut proof = PrfEqDlogs::new(&ctx, &f, &h, &v, &w, &x);
+        let mut proof = PrfEqDlogs::new(&ctx, f, h, v, w, &SecretScalar::new(x));
         proof.r.0 += &DalekScalar::one();
 
         assert!(!proof.verify());
@@ -251,7 +513,7 @@ mod tests {
 
     #[test]
     fn test_prf_dec_complete() {
-        let ctx = CryptoContext::new().unwrap();
+        let ctx = CryptoContext::new();
         let x = ctx.random_scalar();
         let pk = PublicKey::new(ctx.g_to(&x));
 
@@ -260,13 +522,13 @@ mod tests {
         let enc = pk.encrypt(&ctx, &m, &r);
         let dec = enc.c1.scaled(&x);
 
-        let proof = PrfDecryption::new(&ctx, enc, dec, x, pk.y);
+        let proof = PrfDecryption::new(&ctx, ctx.generator(), enc.c1, enc.c2, pk.y, dec, &SecretScalar::new(x));
         assert!(proof.verify());
     }
 
     #[test]
     fn test_prf_dec_sound() {
-        let ctx = CryptoContext::new().unwrap();
+        let ctx = CryptoContext::new();
         let x = ctx.random_scalar();
         let pk = PublicKey::new(ctx.g_to(&x));
 
@@ -275,9 +537,155 @@ mod tests {
         let enc = pk.encrypt(&ctx, &m, &r);
         let dec = enc.c1.scaled(&x);
 
-        let mut proof = PrfDecryption::new(&ctx, enc, dec, x, pk.y);
+        let mut proof = PrfDecryption::new(&ctx, ctx.generator(), enc.c1, enc.c2, pk.y, dec, &SecretScalar::new(x));
         proof.r.0 += &DalekScalar::one();
 
         assert!(!proof.verify());
     }
+
+    #[test]
+    fn test_prf_dec_verify_batch() {
+        let ctx = CryptoContext::new();
+
+        let mut proofs = Vec::new();
+        for _ in 0..5 {
+            let x = ctx.random_scalar();
+            let pk = PublicKey::new(ctx.g_to(&x));
+
+            let m = ctx.random_elem();
+            let r = ctx.random_scalar();
+            let enc = pk.encrypt(&ctx, &m, &r);
+            let dec = enc.c1.scaled(&x);
+
+            proofs.push(PrfDecryption::new(&ctx, ctx.generator(), enc.c1, enc.c2, pk.y, dec, &SecretScalar::new(x)));
+        }
+
+        assert!(PrfDecryption::verify_batch(&proofs));
+
+        proofs[2].r.0 += &DalekScalar::one();
+        assert!(!PrfDecryption::verify_batch(&proofs));
+
+        let results = PrfDecryption::verify_each(&proofs);
+        assert_eq!(results, vec![true, true, false, true, true]);
+    }
+
+    #[test]
+    fn test_prf_one_of_many_complete() {
+        let ctx = CryptoContext::new();
+        let x = ctx.random_scalar();
+        let y = ctx.g_to(&x);
+        let pk = PublicKey::new(y.clone());
+
+        let zero = ctx.g_to(&Scalar(DalekScalar::zero()));
+        let one = ctx.generator();
+        let plaintexts = vec![zero, one.clone()];
+
+        let index = 1;
+        let r = ctx.random_scalar();
+        let ct = pk.encrypt(&ctx, &plaintexts[index], &r);
+
+        let proof = PrfOneOfMany::new(&ctx, ct, y, plaintexts, index, &r);
+        assert!(proof.verify());
+    }
+
+    #[test]
+    fn test_prf_one_of_many_sound() {
+        let ctx = CryptoContext::new();
+        let x = ctx.random_scalar();
+        let y = ctx.g_to(&x);
+        let pk = PublicKey::new(y.clone());
+
+        let zero = ctx.g_to(&Scalar(DalekScalar::zero()));
+        let one = ctx.generator();
+        let plaintexts = vec![zero, one.clone()];
+
+        let index = 1;
+        let r = ctx.random_scalar();
+        let ct = pk.encrypt(&ctx, &plaintexts[index], &r);
+
+        let mut proof = PrfOneOfMany::new(&ctx, ct, y, plaintexts, index, &r);
+        proof.branches[0].r.0 += &DalekScalar::one();
+        assert!(!proof.verify());
+    }
+
+    #[test]
+    fn test_prf_unit_vector_complete() {
+        let ctx = CryptoContext::new();
+        let x = ctx.random_scalar();
+        let y = ctx.g_to(&x);
+        let pk = PublicKey::new(y.clone());
+
+        let zero = ctx.g_to(&Scalar(DalekScalar::zero()));
+        let one = ctx.generator();
+
+        let n = 3;
+        let index = 1;
+        let randomizers: Vec<_> = (0..n).map(|_| ctx.random_scalar()).collect();
+        let cts: Vec<_> = (0..n).map(|i| {
+            let m = if i == index { &one } else { &zero };
+            pk.encrypt(&ctx, m, &randomizers[i])
+        }).collect();
+
+        let proof = PrfUnitVector::new(&ctx, y, cts, index, &randomizers);
+        assert!(proof.verify());
+    }
+
+    #[test]
+    fn test_prf_unit_vector_sound_tampered_branch() {
+        let ctx = CryptoContext::new();
+        let x = ctx.random_scalar();
+        let y = ctx.g_to(&x);
+        let pk = PublicKey::new(y.clone());
+
+        let zero = ctx.g_to(&Scalar(DalekScalar::zero()));
+        let one = ctx.generator();
+
+        let n = 3;
+        let index = 1;
+        let randomizers: Vec<_> = (0..n).map(|_| ctx.random_scalar()).collect();
+        let cts: Vec<_> = (0..n).map(|i| {
+            let m = if i == index { &one } else { &zero };
+            pk.encrypt(&ctx, m, &randomizers[i])
+        }).collect();
+
+        let mut proof = PrfUnitVector::new(&ctx, y, cts, index, &randomizers);
+        proof.component_proofs[0].branches[0].r.0 += &DalekScalar::one();
+        assert!(!proof.verify());
+    }
+
+    #[test]
+    fn test_prf_unit_vector_sound_two_ones() {
+        // Two components both proving "1" breaks the unit-vector invariant:
+        // each `PrfOneOfMany` checks out on its own, but the homomorphic sum
+        // no longer encrypts `g^1`, so the sum proof must reject it.
+        let ctx = CryptoContext::new();
+        let x = ctx.random_scalar();
+        let y = ctx.g_to(&x);
+        let pk = PublicKey::new(y.clone());
+
+        let zero = ctx.g_to(&Scalar(DalekScalar::zero()));
+        let one = ctx.generator();
+        let plaintexts = vec![zero.clone(), one.clone()];
+
+        let bits = [1usize, 1, 0];
+        let randomizers: Vec<_> = bits.iter().map(|_| ctx.random_scalar()).collect();
+        let cts: Vec<_> = bits.iter().enumerate().map(|(i, &bit)| {
+            let m = if bit == 1 { &one } else { &zero };
+            pk.encrypt(&ctx, m, &randomizers[i])
+        }).collect();
+
+        let component_proofs: Vec<_> = bits.iter().enumerate().map(|(i, &bit)| {
+            PrfOneOfMany::new(&ctx, cts[i].clone(), y.clone(), plaintexts.clone(), bit, &randomizers[i])
+        }).collect();
+
+        let mut agg_iter = cts.iter();
+        let agg = agg_iter.next().unwrap().clone();
+        let agg = agg_iter.fold(agg, |acc, ct| acc.add(ct));
+        let sum_r = randomizers.iter().fold(Scalar(DalekScalar::zero()), |acc, r| Scalar(acc.0 + r.0));
+        let result2 = &agg.c2 - &one;
+        let sum_proof = PrfEqDlogs::new(&ctx, one.clone(), y.clone(), agg.c1.clone(), result2, &SecretScalar::new(sum_r));
+
+        let forged = PrfUnitVector { g: one, y, cts, component_proofs, sum_proof };
+        assert!(!forged.verify());
+    }
 }